@@ -8,6 +8,7 @@ use tower_http::cors::{Any, CorsLayer};
 use tower_http::trace::TraceLayer;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
+mod middleware;
 mod routes;
 mod models;
 mod services;
@@ -34,6 +35,7 @@ async fn main() {
         .route("/api/v1/simulations", get(routes::simulations::list_simulations))
         .route("/api/v1/simulations/:id", get(routes::simulations::get_simulation))
         .route("/api/v1/simulations/:id/run", post(routes::simulations::run_simulation))
+        .route("/api/v1/simulations/runs/:run_id", get(routes::simulations::get_run))
         // AI assistant
         .route("/api/v1/ai/ask", post(routes::ai::ask_question))
         // User progress
@@ -46,7 +48,8 @@ async fn main() {
                 .allow_origin(Any)
                 .allow_methods(Any)
                 .allow_headers(Any),
-        );
+        )
+        .layer(axum::middleware::from_fn(middleware::request_span));
 
     // Run server
     let addr = SocketAddr::from(([0, 0, 0, 0], 3001));
@@ -0,0 +1,48 @@
+//! Per-request tracing: a root span carrying a generated `request_id` and
+//! `user_id`, threaded through the handlers and services so a single
+//! student-reported issue can be traced end to end through the logs.
+
+use axum::extract::Request;
+use axum::http::HeaderValue;
+use axum::middleware::Next;
+use axum::response::Response;
+use tracing::Instrument;
+use uuid::Uuid;
+
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Carries the root request span and its identifying fields into handlers
+/// via an [`axum::extract::Extension`].
+#[derive(Clone)]
+pub struct RequestContext {
+    pub request_id: String,
+    pub user_id: String,
+    pub span: tracing::Span,
+}
+
+/// Opens a root span for the inbound request and attaches its id to the response.
+pub async fn request_span(mut req: Request, next: Next) -> Response {
+    let request_id = Uuid::new_v4().to_string();
+    // Same demo-user plumbing as `progress`, until real auth lands.
+    let user_id = "demo-user".to_string();
+
+    let span = tracing::info_span!(
+        "request",
+        request_id = %request_id,
+        user_id = %user_id,
+        method = %req.method(),
+        path = %req.uri().path(),
+    );
+
+    req.extensions_mut().insert(RequestContext {
+        request_id: request_id.clone(),
+        user_id,
+        span: span.clone(),
+    });
+
+    let mut response = next.run(req).instrument(span).await;
+    if let Ok(value) = HeaderValue::from_str(&request_id) {
+        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+    response
+}
@@ -0,0 +1,39 @@
+//! Shared data types that aren't specific to a single route or service.
+
+use serde::Serialize;
+
+#[derive(Clone, Serialize)]
+pub struct Simulation {
+    pub id: String,
+    pub title: String,
+    pub description: String,
+    pub category: String,
+}
+
+/// The fixed catalog of simulations this tutorial ships.
+pub fn catalog() -> Vec<Simulation> {
+    vec![
+        Simulation {
+            id: "double-slit".to_string(),
+            title: "Double-Slit Interference".to_string(),
+            description: "Explore wave-particle duality through the classic double-slit experiment.".to_string(),
+            category: "wave-optics".to_string(),
+        },
+        Simulation {
+            id: "quantum-tunneling".to_string(),
+            title: "Quantum Tunneling".to_string(),
+            description: "See how a particle can cross a potential barrier it classically shouldn't.".to_string(),
+            category: "quantum-mechanics".to_string(),
+        },
+        Simulation {
+            id: "hydrogen-atom".to_string(),
+            title: "Hydrogen Atom Orbitals".to_string(),
+            description: "Visualize electron probability densities for different quantum numbers.".to_string(),
+            category: "atomic-physics".to_string(),
+        },
+    ]
+}
+
+pub fn find(id: &str) -> Option<Simulation> {
+    catalog().into_iter().find(|sim| sim.id == id)
+}
@@ -0,0 +1,3 @@
+pub mod ai;
+pub mod progress;
+pub mod simulations;
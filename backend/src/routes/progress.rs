@@ -1,14 +1,45 @@
-use axum::{http::StatusCode, Json};
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+use axum::{extract::Extension, http::StatusCode, Json};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use chrono::{DateTime, Utc};
+use tracing::Instrument;
+
+use crate::middleware::RequestContext;
+
+/// Most recent simulation run per user, so `get_progress` can resume it.
+static LAST_RUNS: OnceLock<Mutex<HashMap<String, CurrentSimulation>>> = OnceLock::new();
+
+fn last_runs() -> &'static Mutex<HashMap<String, CurrentSimulation>> {
+    LAST_RUNS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Record the parameters and output of the most recent simulation run for
+/// `user_id`, called from `routes::simulations::run_simulation` after each
+/// run, so a resumed session can replay both what was set and what it showed.
+pub fn record_last_run(user_id: &str, simulation_id: &str, parameters: Value, output: Value) {
+    last_runs().lock().unwrap().insert(
+        user_id.to_string(),
+        CurrentSimulation {
+            simulation_id: simulation_id.to_string(),
+            started_at: Utc::now(),
+            last_parameters: Some(parameters),
+            last_output: Some(output),
+        },
+    );
+}
 
 /// Get user progress (mock implementation for MVP)
 pub async fn get_progress() -> Json<UserProgress> {
     // TODO: Get from database based on authenticated user
+    let current_simulation = last_runs().lock().unwrap().get("demo-user").cloned();
+
     Json(UserProgress {
         user_id: "demo-user".to_string(),
         completed_simulations: vec![],
-        current_simulation: None,
+        current_simulation,
         total_time_minutes: 0,
         achievements: vec![],
         last_activity: Utc::now(),
@@ -17,48 +48,63 @@ pub async fn get_progress() -> Json<UserProgress> {
 
 /// Save user progress
 pub async fn save_progress(
+    Extension(ctx): Extension<RequestContext>,
     Json(request): Json<SaveProgressRequest>,
 ) -> Result<Json<UserProgress>, StatusCode> {
-    // TODO: Save to database
-    tracing::info!(
-        "Saving progress: simulation={}, completed={}",
-        request.simulation_id,
-        request.completed
+    let span = tracing::info_span!(
+        parent: &ctx.span,
+        "save_progress",
+        simulation_id = %request.simulation_id,
+        completed = request.completed,
+        achievements_earned = tracing::field::Empty,
     );
-    
-    // Check achievements BEFORE moving request fields
-    let achievements = check_achievements(&request);
-    
-    // Clone parameters before moving
-    let parameters = request.parameters.clone();
-    
-    let progress = UserProgress {
-        user_id: "demo-user".to_string(),
-        completed_simulations: if request.completed {
-            vec![CompletedSimulation {
-                simulation_id: request.simulation_id.clone(),
-                completed_at: Utc::now(),
-                score: request.score,
-                time_spent_minutes: request.time_spent_minutes,
-            }]
-        } else {
-            vec![]
-        },
-        current_simulation: if !request.completed {
-            Some(CurrentSimulation {
-                simulation_id: request.simulation_id,
-                started_at: Utc::now(),
-                last_parameters: parameters,
-            })
-        } else {
-            None
-        },
-        total_time_minutes: request.time_spent_minutes,
-        achievements,
-        last_activity: Utc::now(),
-    };
-    
-    Ok(Json(progress))
+
+    async move {
+        // TODO: Save to database
+        tracing::info!(
+            "Saving progress: simulation={}, completed={}",
+            request.simulation_id,
+            request.completed
+        );
+
+        // Check achievements BEFORE moving request fields
+        let achievements = check_achievements(&request);
+        tracing::Span::current().record("achievements_earned", achievements.len());
+
+        // Clone parameters before moving
+        let parameters = request.parameters.clone();
+
+        let progress = UserProgress {
+            user_id: ctx.user_id,
+            completed_simulations: if request.completed {
+                vec![CompletedSimulation {
+                    simulation_id: request.simulation_id.clone(),
+                    completed_at: Utc::now(),
+                    score: request.score,
+                    time_spent_minutes: request.time_spent_minutes,
+                }]
+            } else {
+                vec![]
+            },
+            current_simulation: if !request.completed {
+                Some(CurrentSimulation {
+                    simulation_id: request.simulation_id,
+                    started_at: Utc::now(),
+                    last_parameters: parameters,
+                    last_output: None,
+                })
+            } else {
+                None
+            },
+            total_time_minutes: request.time_spent_minutes,
+            achievements,
+            last_activity: Utc::now(),
+        };
+
+        Ok(Json(progress))
+    }
+    .instrument(span)
+    .await
 }
 
 fn check_achievements(request: &SaveProgressRequest) -> Vec<Achievement> {
@@ -109,11 +155,12 @@ pub struct CompletedSimulation {
     pub time_spent_minutes: u32,
 }
 
-#[derive(Serialize)]
+#[derive(Clone, Serialize)]
 pub struct CurrentSimulation {
     pub simulation_id: String,
     pub started_at: DateTime<Utc>,
     pub last_parameters: Option<serde_json::Value>,
+    pub last_output: Option<serde_json::Value>,
 }
 
 #[derive(Serialize)]
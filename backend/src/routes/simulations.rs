@@ -0,0 +1,76 @@
+use axum::{
+    extract::{Extension, Path},
+    http::StatusCode,
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::Instrument;
+
+use crate::middleware::RequestContext;
+use crate::models;
+use crate::routes::progress;
+use crate::services::sim::{self, SimError};
+
+/// List the simulation catalog.
+pub async fn list_simulations() -> Json<Vec<models::Simulation>> {
+    Json(models::catalog())
+}
+
+/// Get a single simulation's description.
+pub async fn get_simulation(Path(id): Path<String>) -> Result<Json<models::Simulation>, StatusCode> {
+    models::find(&id).map(Json).ok_or(StatusCode::NOT_FOUND)
+}
+
+/// Run a simulation with the given parameters and return its computed results.
+pub async fn run_simulation(
+    Extension(ctx): Extension<RequestContext>,
+    Path(id): Path<String>,
+    Json(request): Json<RunSimulationRequest>,
+) -> Result<Json<RunSimulationResponse>, StatusCode> {
+    let span = tracing::info_span!(parent: &ctx.span, "run_simulation", simulation_id = %id);
+
+    async move {
+        if models::find(&id).is_none() {
+            return Err(StatusCode::NOT_FOUND);
+        }
+
+        let parameters = request.parameters.unwrap_or_else(|| Value::Object(Default::default()));
+        let result = sim::execute(id, parameters.clone()).await.map_err(|err| match err {
+            SimError::UnknownSimulation(_) => StatusCode::NOT_FOUND,
+        })?;
+
+        progress::record_last_run(&ctx.user_id, &result.simulation_id, parameters, result.output.clone());
+
+        Ok(Json(RunSimulationResponse {
+            run_id: result.run_id,
+            simulation_id: result.simulation_id,
+            output: result.output,
+        }))
+    }
+    .instrument(span)
+    .await
+}
+
+/// Replay a previously computed run by its id.
+pub async fn get_run(Path(run_id): Path<u64>) -> Result<Json<RunSimulationResponse>, StatusCode> {
+    let result = sim::replay(run_id).ok_or(StatusCode::NOT_FOUND)?;
+
+    Ok(Json(RunSimulationResponse {
+        run_id: result.run_id,
+        simulation_id: result.simulation_id,
+        output: result.output,
+    }))
+}
+
+#[derive(Deserialize)]
+pub struct RunSimulationRequest {
+    pub parameters: Option<Value>,
+}
+
+#[derive(Serialize)]
+pub struct RunSimulationResponse {
+    pub run_id: u64,
+    pub simulation_id: String,
+    pub output: Value,
+}
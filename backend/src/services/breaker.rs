@@ -0,0 +1,87 @@
+//! Per-host circuit breaker for outbound calls to upstreams like the LLM API.
+//!
+//! Tracks consecutive failures per authority (e.g. `api.openai.com`) and
+//! trips the breaker after too many in a row, so a flaky upstream degrades
+//! callers to their offline fallback instead of retrying into it forever.
+
+use std::time::{Duration, Instant};
+
+use dashmap::DashMap;
+
+const FAILURE_THRESHOLD: u32 = 5;
+const BASE_OPEN_DURATION: Duration = Duration::from_secs(1);
+const MAX_OPEN_DURATION: Duration = Duration::from_secs(180);
+
+struct Breaker {
+    consecutive_failures: u32,
+    open_until: Option<Instant>,
+}
+
+impl Breaker {
+    fn new() -> Self {
+        Self {
+            consecutive_failures: 0,
+            open_until: None,
+        }
+    }
+
+    fn should_try(&self) -> bool {
+        match self.open_until {
+            Some(until) => Instant::now() >= until,
+            None => true,
+        }
+    }
+
+    fn on_success(&mut self) {
+        self.consecutive_failures = 0;
+        self.open_until = None;
+    }
+
+    fn on_failure(&mut self) {
+        self.consecutive_failures += 1;
+        if self.consecutive_failures >= FAILURE_THRESHOLD {
+            let backoff_exponent = self.consecutive_failures - FAILURE_THRESHOLD;
+            let backoff = BASE_OPEN_DURATION
+                .saturating_mul(1 << backoff_exponent.min(8))
+                .min(MAX_OPEN_DURATION);
+            self.open_until = Some(Instant::now() + backoff);
+        }
+    }
+}
+
+/// Circuit breakers for outbound upstreams, keyed by host authority.
+#[derive(Default)]
+pub struct Breakers {
+    breakers: DashMap<String, Breaker>,
+}
+
+impl Breakers {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// True when `host` is closed, or half-open because its open window elapsed.
+    #[tracing::instrument(skip(self))]
+    pub fn should_try(&self, host: &str) -> bool {
+        self.breakers
+            .entry(host.to_string())
+            .or_insert_with(Breaker::new)
+            .should_try()
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub fn succeed(&self, host: &str) {
+        self.breakers
+            .entry(host.to_string())
+            .or_insert_with(Breaker::new)
+            .on_success();
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub fn fail(&self, host: &str) {
+        self.breakers
+            .entry(host.to_string())
+            .or_insert_with(Breaker::new)
+            .on_failure();
+    }
+}
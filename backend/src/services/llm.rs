@@ -0,0 +1,512 @@
+//! Drives the external assistant API used by `routes::ai`.
+//!
+//! Modeled on the OpenAI Assistants lifecycle: each student gets a
+//! persistent *thread*, their question is appended as a message, and a
+//! *run* is started against a physics-tutor *assistant*. We poll the run
+//! until it completes and read back the reply (or a structured tool
+//! call carrying the reply plus related topics / suggested experiments).
+//!
+//! When no API key is configured, or the upstream call fails, we fall
+//! back to the original offline keyword matcher so the tutor keeps
+//! answering questions.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::routes::ai::SuggestedExperiment;
+use crate::services::breaker::Breakers;
+
+const OPENAI_HOST: &str = "api.openai.com";
+const OPENAI_BASE_URL: &str = "https://api.openai.com/v1";
+const OPENAI_BETA_HEADER: &str = "assistants=v2";
+const RUN_POLL_INTERVAL: Duration = Duration::from_millis(500);
+const RUN_POLL_MAX_ATTEMPTS: u32 = 20;
+const CALL_ATTEMPTS: u32 = 2;
+/// Upper bound on a single HTTP round-trip, so a stuck upstream fails fast
+/// instead of hanging the request (and the breaker) indefinitely.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+const ASSISTANT_INSTRUCTIONS: &str = "You are a patient physics tutor helping a student explore \
+interactive quantum mechanics simulations (double-slit interference, quantum tunneling, and \
+hydrogen atom orbitals). Answer in terms of what the student can observe and adjust in the \
+simulation they are currently looking at. When you reply, call the `physics_tutor_reply` \
+function with your answer plus related topics and suggested experiments rather than replying \
+in plain text.";
+
+/// Per-student thread ids, keyed the same way as the demo `user_id` in `progress`.
+static THREADS: OnceLock<Mutex<HashMap<String, String>>> = OnceLock::new();
+
+fn threads() -> &'static Mutex<HashMap<String, String>> {
+    THREADS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+static BREAKERS: OnceLock<Breakers> = OnceLock::new();
+
+fn breakers() -> &'static Breakers {
+    BREAKERS.get_or_init(Breakers::new)
+}
+
+pub struct AskQuestionOutcome {
+    pub answer: String,
+    pub related_topics: Vec<String>,
+    pub suggested_experiments: Vec<SuggestedExperiment>,
+}
+
+/// Errors from a single assistant round-trip, distinct from a hard HTTP
+/// failure so the breaker and retry loop can treat "upstream never finished"
+/// the same way as a connection error rather than silently as a success.
+#[derive(Debug)]
+enum LlmError {
+    Http(reqwest::Error),
+    /// The run produced no usable reply: it did not reach a terminal status
+    /// within `RUN_POLL_MAX_ATTEMPTS`, it ended as failed, cancelled or
+    /// expired, or it completed without any reply text.
+    PollTimedOut,
+}
+
+impl fmt::Display for LlmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LlmError::Http(err) => write!(f, "{err}"),
+            LlmError::PollTimedOut => write!(f, "run did not produce a reply"),
+        }
+    }
+}
+
+impl std::error::Error for LlmError {}
+
+impl From<reqwest::Error> for LlmError {
+    fn from(err: reqwest::Error) -> Self {
+        LlmError::Http(err)
+    }
+}
+
+/// Answer `question` on behalf of `user_id`, grounding the run in `context`
+/// (the simulation id / last parameters the student is currently looking at).
+///
+/// Falls back to the offline keyword matcher when `OPENAI_API_KEY` is unset
+/// or the upstream call fails.
+#[tracing::instrument(skip(question, context), fields(question_len = question.len()))]
+pub async fn ask_question(user_id: &str, question: &str, context: Option<&str>) -> AskQuestionOutcome {
+    let Ok(api_key) = std::env::var("OPENAI_API_KEY") else {
+        tracing::debug!("OPENAI_API_KEY not set, using offline answers");
+        return fallback::answer(question);
+    };
+    if api_key.is_empty() {
+        tracing::debug!("OPENAI_API_KEY not set, using offline answers");
+        return fallback::answer(question);
+    }
+
+    if !breakers().should_try(OPENAI_HOST) {
+        tracing::warn!(host = OPENAI_HOST, "circuit breaker open, falling back to offline answers");
+        return fallback::answer(question);
+    }
+
+    let client = Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .expect("reqwest client with timeout should build");
+
+    // Establish the thread and post the question once, outside the retry
+    // loop below: retrying this step would re-post the same question to the
+    // (reused, cross-request) thread every time a later step timed out.
+    let thread_id = match ensure_thread(&client, &api_key, user_id).await {
+        Ok(thread_id) => thread_id,
+        Err(err) => {
+            breakers().fail(OPENAI_HOST);
+            tracing::warn!(error = %err, "failed to open LLM thread, falling back to offline answers");
+            return fallback::answer(question);
+        }
+    };
+
+    if let Err(err) = add_message(&client, &api_key, &thread_id, question).await {
+        breakers().fail(OPENAI_HOST);
+        tracing::warn!(error = %err, "failed to post question to LLM thread, falling back to offline answers");
+        return fallback::answer(question);
+    }
+
+    let assistant_id = std::env::var("OPENAI_ASSISTANT_ID").unwrap_or_default();
+    let instructions = match context {
+        Some(context) => format!("{ASSISTANT_INSTRUCTIONS}\n\nCurrent context: {context}"),
+        None => ASSISTANT_INSTRUCTIONS.to_string(),
+    };
+
+    for attempt in 1..=CALL_ATTEMPTS {
+        match run_assistant(&client, &api_key, &thread_id, &assistant_id, &instructions).await {
+            Ok(outcome) => {
+                breakers().succeed(OPENAI_HOST);
+                return outcome;
+            }
+            Err(err) => {
+                breakers().fail(OPENAI_HOST);
+                tracing::warn!(error = %err, attempt, "LLM call failed");
+                if attempt < CALL_ATTEMPTS && !breakers().should_try(OPENAI_HOST) {
+                    break;
+                }
+            }
+        }
+    }
+
+    tracing::warn!("LLM call exhausted retries, falling back to offline answers");
+    fallback::answer(question)
+}
+
+/// Start a new run against the already-prepared thread and return the
+/// structured reply; each call, including a retry, starts a fresh run.
+/// Safe to retry: it neither creates a thread nor posts a message.
+#[tracing::instrument(skip(api_key, thread_id, instructions))]
+async fn run_assistant(
+    client: &Client,
+    api_key: &str,
+    thread_id: &str,
+    assistant_id: &str,
+    instructions: &str,
+) -> Result<AskQuestionOutcome, LlmError> {
+    let run_id = create_run(client, api_key, thread_id, assistant_id, instructions).await?;
+    let tool_args = poll_run(client, api_key, thread_id, &run_id).await?;
+
+    Ok(AskQuestionOutcome {
+        answer: tool_args.answer,
+        related_topics: tool_args.related_topics,
+        suggested_experiments: tool_args
+            .suggested_experiments
+            .into_iter()
+            .map(|e| SuggestedExperiment {
+                simulation_id: e.simulation_id,
+                title: e.title,
+                description: e.description,
+            })
+            .collect(),
+    })
+}
+
+async fn ensure_thread(client: &Client, api_key: &str, user_id: &str) -> Result<String, reqwest::Error> {
+    if let Some(thread_id) = threads().lock().unwrap().get(user_id).cloned() {
+        return Ok(thread_id);
+    }
+
+    let response: Value = client
+        .post(format!("{OPENAI_BASE_URL}/threads"))
+        .bearer_auth(api_key)
+        .header("OpenAI-Beta", OPENAI_BETA_HEADER)
+        .json(&json!({}))
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    let thread_id = response["id"].as_str().unwrap_or_default().to_string();
+    threads()
+        .lock()
+        .unwrap()
+        .insert(user_id.to_string(), thread_id.clone());
+    Ok(thread_id)
+}
+
+async fn add_message(client: &Client, api_key: &str, thread_id: &str, question: &str) -> Result<(), reqwest::Error> {
+    client
+        .post(format!("{OPENAI_BASE_URL}/threads/{thread_id}/messages"))
+        .bearer_auth(api_key)
+        .header("OpenAI-Beta", OPENAI_BETA_HEADER)
+        .json(&json!({ "role": "user", "content": question }))
+        .send()
+        .await?
+        .error_for_status()?;
+    Ok(())
+}
+
+async fn create_run(
+    client: &Client,
+    api_key: &str,
+    thread_id: &str,
+    assistant_id: &str,
+    instructions: &str,
+) -> Result<String, reqwest::Error> {
+    let response: Value = client
+        .post(format!("{OPENAI_BASE_URL}/threads/{thread_id}/runs"))
+        .bearer_auth(api_key)
+        .header("OpenAI-Beta", OPENAI_BETA_HEADER)
+        .json(&json!({
+            "assistant_id": assistant_id,
+            "instructions": instructions,
+            "tools": [{
+                "type": "function",
+                "function": {
+                    "name": "physics_tutor_reply",
+                    "parameters": {
+                        "type": "object",
+                        "properties": {
+                            "answer": { "type": "string" },
+                            "related_topics": { "type": "array", "items": { "type": "string" } },
+                            "suggested_experiments": {
+                                "type": "array",
+                                "items": {
+                                    "type": "object",
+                                    "properties": {
+                                        "simulation_id": { "type": "string" },
+                                        "title": { "type": "string" },
+                                        "description": { "type": "string" }
+                                    },
+                                    "required": ["simulation_id", "title", "description"]
+                                }
+                            }
+                        },
+                        "required": ["answer", "related_topics", "suggested_experiments"]
+                    }
+                }
+            }]
+        }))
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    Ok(response["id"].as_str().unwrap_or_default().to_string())
+}
+
+async fn poll_run(
+    client: &Client,
+    api_key: &str,
+    thread_id: &str,
+    run_id: &str,
+) -> Result<ToolCallArgs, LlmError> {
+    for _ in 0..RUN_POLL_MAX_ATTEMPTS {
+        let run: Value = client
+            .get(format!("{OPENAI_BASE_URL}/threads/{thread_id}/runs/{run_id}"))
+            .bearer_auth(api_key)
+            .header("OpenAI-Beta", OPENAI_BETA_HEADER)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        match run["status"].as_str().unwrap_or_default() {
+            "requires_action" => {
+                if let Some(args) = extract_tool_call_args(&run) {
+                    return Ok(args);
+                }
+            }
+            "completed" => {
+                // The assistant finished without calling `physics_tutor_reply` as
+                // instructed; use whatever plain-text reply it left in the
+                // thread instead of discarding it.
+                return match latest_message_text(client, api_key, thread_id).await? {
+                    Some(text) if !text.trim().is_empty() => Ok(ToolCallArgs {
+                        answer: text,
+                        related_topics: vec![],
+                        suggested_experiments: vec![],
+                    }),
+                    _ => Err(LlmError::PollTimedOut),
+                };
+            }
+            "failed" | "cancelled" | "expired" => return Err(LlmError::PollTimedOut),
+            _ => {}
+        }
+
+        tokio::time::sleep(RUN_POLL_INTERVAL).await;
+    }
+
+    // Ran out of polling attempts without the run reaching a terminal status.
+    // Treat this as a failure (not an empty success) so the retry loop in
+    // `ask_question` records it with the breaker instead of silently
+    // `succeed`-ing on a request that never actually finished.
+    Err(LlmError::PollTimedOut)
+}
+
+/// Fetch the most recent message in `thread_id` — the assistant's plain-text
+/// reply, when the run completed without calling `physics_tutor_reply`.
+async fn latest_message_text(
+    client: &Client,
+    api_key: &str,
+    thread_id: &str,
+) -> Result<Option<String>, LlmError> {
+    let response: Value = client
+        .get(format!("{OPENAI_BASE_URL}/threads/{thread_id}/messages?limit=1&order=desc"))
+        .bearer_auth(api_key)
+        .header("OpenAI-Beta", OPENAI_BETA_HEADER)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    Ok(response["data"][0]["content"][0]["text"]["value"]
+        .as_str()
+        .map(str::to_string))
+}
+
+fn extract_tool_call_args(run: &Value) -> Option<ToolCallArgs> {
+    let call = run["required_action"]["submit_tool_outputs"]["tool_calls"]
+        .as_array()?
+        .first()?;
+    let raw_args = call["function"]["arguments"].as_str()?;
+    serde_json::from_str(raw_args).ok()
+}
+
+#[derive(Deserialize)]
+struct ToolCallArgs {
+    answer: String,
+    related_topics: Vec<String>,
+    suggested_experiments: Vec<ToolCallExperiment>,
+}
+
+#[derive(Deserialize)]
+struct ToolCallExperiment {
+    simulation_id: String,
+    title: String,
+    description: String,
+}
+
+/// Offline fallback used when no API key is configured or the LLM call fails.
+mod fallback {
+    use super::AskQuestionOutcome;
+    use crate::routes::ai::SuggestedExperiment;
+
+    pub fn answer(question: &str) -> AskQuestionOutcome {
+        AskQuestionOutcome {
+            answer: get_physics_answer(question),
+            related_topics: get_related_topics(question),
+            suggested_experiments: get_suggested_experiments(question),
+        }
+    }
+
+    fn get_physics_answer(question: &str) -> String {
+        let q = question.to_lowercase();
+
+        if q.contains("interference") || q.contains("интерференц") {
+            return r#"**Interference** occurs when two or more waves overlap, resulting in a new wave pattern.
+
+In the double-slit experiment:
+- When waves are in phase (crests align), they create **constructive interference** (bright bands)
+- When waves are out of phase (crest meets trough), they create **destructive interference** (dark bands)
+
+The spacing of the interference pattern depends on:
+- Wavelength of light (λ)
+- Distance between slits (d)
+- Distance to the screen (L)
+
+Try adjusting the wavelength slider to see how the pattern changes!"#.to_string();
+        }
+
+        if q.contains("wave") && q.contains("particle") || q.contains("duality") {
+            return r#"**Wave-particle duality** is one of the most fundamental concepts in quantum mechanics.
+
+It means that quantum objects (like electrons, photons) exhibit both wave-like and particle-like properties:
+
+1. **As waves**: They can interfere, diffract, and create patterns
+2. **As particles**: They hit detectors at specific points
+
+The key insight: **observation matters**! When we try to determine which slit a particle passes through, the interference pattern disappears.
+
+This is demonstrated beautifully in the double-slit experiment. Try turning on "Observer Mode" to see the difference!"#.to_string();
+        }
+
+        if q.contains("tunnel") || q.contains("barrier") {
+            return r#"**Quantum tunneling** is a phenomenon where a particle can pass through a potential barrier even if its energy is less than the barrier height.
+
+Classically, this is impossible - imagine a ball rolling toward a hill without enough energy to go over it.
+
+In quantum mechanics, the particle's wave function extends beyond the barrier, giving a non-zero probability of finding the particle on the other side.
+
+**Key factors affecting tunneling probability:**
+- Barrier height (higher = less tunneling)
+- Barrier width (wider = less tunneling)
+- Particle mass (heavier = less tunneling)
+- Particle energy (higher = more tunneling)
+
+Try the Quantum Tunneling simulation to explore these relationships!"#.to_string();
+        }
+
+        if q.contains("orbital") || q.contains("electron") && q.contains("atom") {
+            return r#"**Atomic orbitals** are regions of space where electrons are most likely to be found.
+
+In the hydrogen atom:
+- **s orbitals**: Spherical, can hold 2 electrons
+- **p orbitals**: Dumbbell-shaped, can hold 6 electrons
+- **d orbitals**: More complex shapes, can hold 10 electrons
+
+The shapes are determined by the wave function solutions to the Schrödinger equation.
+
+Each orbital is characterized by quantum numbers:
+- n (principal): energy level
+- l (angular momentum): shape
+- m (magnetic): orientation
+
+Explore the Hydrogen Atom simulation to see these orbitals in 3D!"#.to_string();
+        }
+
+        format!(r#"That's a great question about physics!
+
+Based on your question: "{}"
+
+I'd recommend exploring the relevant simulation to build intuition. You can:
+1. Adjust parameters and observe changes
+2. Read the theory section for mathematical details
+3. Ask more specific questions about what you observe
+
+What aspect would you like to explore further?"#, question)
+    }
+
+    fn get_related_topics(question: &str) -> Vec<String> {
+        let q = question.to_lowercase();
+
+        if q.contains("interference") || q.contains("slit") {
+            vec![
+                "Wave-particle duality".to_string(),
+                "Quantum superposition".to_string(),
+                "Wave function collapse".to_string(),
+                "Heisenberg uncertainty principle".to_string(),
+            ]
+        } else if q.contains("tunnel") {
+            vec![
+                "Potential barriers".to_string(),
+                "Schrödinger equation".to_string(),
+                "Alpha decay".to_string(),
+                "Scanning tunneling microscope".to_string(),
+            ]
+        } else if q.contains("orbital") || q.contains("atom") {
+            vec![
+                "Quantum numbers".to_string(),
+                "Electron configuration".to_string(),
+                "Spectral lines".to_string(),
+                "Bohr model".to_string(),
+            ]
+        } else {
+            vec![
+                "Quantum mechanics basics".to_string(),
+                "Wave function".to_string(),
+                "Probability in quantum physics".to_string(),
+            ]
+        }
+    }
+
+    fn get_suggested_experiments(question: &str) -> Vec<SuggestedExperiment> {
+        let q = question.to_lowercase();
+
+        if q.contains("interference") || q.contains("slit") || q.contains("wave") {
+            vec![
+                SuggestedExperiment {
+                    simulation_id: "double-slit".to_string(),
+                    title: "Vary the wavelength".to_string(),
+                    description: "Change the wavelength from 400nm to 700nm and observe how the interference pattern spacing changes".to_string(),
+                },
+                SuggestedExperiment {
+                    simulation_id: "double-slit".to_string(),
+                    title: "Toggle observer mode".to_string(),
+                    description: "Turn observer mode on and off to see the dramatic difference between wave and particle behavior".to_string(),
+                },
+            ]
+        } else {
+            vec![]
+        }
+    }
+}
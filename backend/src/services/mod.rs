@@ -0,0 +1,3 @@
+pub mod breaker;
+pub mod llm;
+pub mod sim;
@@ -0,0 +1,195 @@
+//! Executes the physics models behind `routes::simulations::run_simulation`.
+//!
+//! Modeled as a small kernel/interpreter: a long-lived worker task accepts
+//! execution requests (simulation id + JSON parameters) over a channel,
+//! runs the matching computation, and hands back a structured result
+//! tagged with an incrementing run id. Recent results are cached so a
+//! run can be replayed by id instead of recomputed.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use tokio::sync::{mpsc, oneshot};
+
+/// How many recent runs we keep around for replay.
+const RESULT_CACHE_CAPACITY: usize = 100;
+
+#[derive(Debug)]
+pub enum SimError {
+    UnknownSimulation(String),
+}
+
+#[derive(Clone, Serialize)]
+pub struct ExecutionResult {
+    pub run_id: u64,
+    pub simulation_id: String,
+    pub parameters: Value,
+    pub output: Value,
+}
+
+struct ExecutionRequest {
+    simulation_id: String,
+    parameters: Value,
+    reply: oneshot::Sender<Result<ExecutionResult, SimError>>,
+}
+
+static WORKER: OnceLock<mpsc::Sender<ExecutionRequest>> = OnceLock::new();
+static NEXT_RUN_ID: AtomicU64 = AtomicU64::new(1);
+static RESULT_CACHE: OnceLock<Mutex<HashMap<u64, ExecutionResult>>> = OnceLock::new();
+
+fn result_cache() -> &'static Mutex<HashMap<u64, ExecutionResult>> {
+    RESULT_CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn worker() -> &'static mpsc::Sender<ExecutionRequest> {
+    WORKER.get_or_init(|| {
+        let (tx, mut rx) = mpsc::channel::<ExecutionRequest>(32);
+        tokio::spawn(async move {
+            while let Some(request) = rx.recv().await {
+                let result = run(request.simulation_id, request.parameters);
+                let _ = request.reply.send(result);
+            }
+        });
+        tx
+    })
+}
+
+/// Execute `simulation_id` against `parameters`, returning the tagged result.
+#[tracing::instrument(skip(parameters))]
+pub async fn execute(simulation_id: String, parameters: Value) -> Result<ExecutionResult, SimError> {
+    let (reply_tx, reply_rx) = oneshot::channel();
+    let request = ExecutionRequest {
+        simulation_id,
+        parameters,
+        reply: reply_tx,
+    };
+
+    worker()
+        .send(request)
+        .await
+        .expect("sim worker task should not exit while the channel is held");
+
+    reply_rx
+        .await
+        .expect("sim worker should always reply")
+}
+
+/// Look up a previously cached run by id.
+pub fn replay(run_id: u64) -> Option<ExecutionResult> {
+    result_cache().lock().unwrap().get(&run_id).cloned()
+}
+
+fn run(simulation_id: String, parameters: Value) -> Result<ExecutionResult, SimError> {
+    let output = match simulation_id.as_str() {
+        "double-slit" => double_slit(&parameters),
+        "quantum-tunneling" => quantum_tunneling(&parameters),
+        "hydrogen-atom" => hydrogen_orbital(&parameters),
+        other => return Err(SimError::UnknownSimulation(other.to_string())),
+    };
+
+    let run_id = NEXT_RUN_ID.fetch_add(1, Ordering::Relaxed);
+    let result = ExecutionResult {
+        run_id,
+        simulation_id,
+        parameters,
+        output,
+    };
+
+    let mut cache = result_cache().lock().unwrap();
+    if cache.len() >= RESULT_CACHE_CAPACITY {
+        if let Some(&oldest) = cache.keys().min() {
+            cache.remove(&oldest);
+        }
+    }
+    cache.insert(run_id, result.clone());
+
+    Ok(result)
+}
+
+fn param_f64(parameters: &Value, key: &str, default: f64) -> f64 {
+    parameters.get(key).and_then(Value::as_f64).unwrap_or(default)
+}
+
+fn param_usize(parameters: &Value, key: &str, default: usize) -> usize {
+    parameters
+        .get(key)
+        .and_then(Value::as_u64)
+        .map(|v| v as usize)
+        .unwrap_or(default)
+}
+
+/// Interference intensity across the screen for a double-slit setup.
+fn double_slit(parameters: &Value) -> Value {
+    let wavelength_nm = param_f64(parameters, "wavelength_nm", 500.0);
+    let slit_separation_um = param_f64(parameters, "slit_separation_um", 20.0);
+    let screen_distance_m = param_f64(parameters, "screen_distance_m", 1.0);
+    let screen_width_m = param_f64(parameters, "screen_width_m", 0.05);
+    let num_points = param_usize(parameters, "num_points", 200).max(2);
+
+    let wavelength_m = wavelength_nm * 1e-9;
+    let slit_separation_m = slit_separation_um * 1e-6;
+
+    let intensity: Vec<f64> = (0..num_points)
+        .map(|i| {
+            let x = screen_width_m * (i as f64 / (num_points - 1) as f64 - 0.5);
+            let path_difference = slit_separation_m * x / screen_distance_m;
+            let phase = std::f64::consts::PI * path_difference / wavelength_m;
+            phase.cos().powi(2)
+        })
+        .collect();
+
+    json!({ "intensity": intensity })
+}
+
+/// Transmission coefficient for a particle tunneling through a rectangular barrier.
+///
+/// Uses reduced units (particle mass and ħ both 1) since this drives a
+/// teaching simulation rather than a precision calculation.
+fn quantum_tunneling(parameters: &Value) -> Value {
+    let barrier_height_ev = param_f64(parameters, "barrier_height_ev", 5.0);
+    let barrier_width_nm = param_f64(parameters, "barrier_width_nm", 1.0);
+    let particle_energy_ev = param_f64(parameters, "particle_energy_ev", 2.0);
+
+    let transmission = if particle_energy_ev >= barrier_height_ev {
+        1.0
+    } else {
+        let k2 = (2.0 * (barrier_height_ev - particle_energy_ev)).sqrt();
+        let sinh_term = (k2 * barrier_width_nm).sinh();
+        let denom = 1.0
+            + (barrier_height_ev.powi(2) * sinh_term.powi(2))
+                / (4.0 * particle_energy_ev * (barrier_height_ev - particle_energy_ev));
+        1.0 / denom
+    };
+
+    json!({ "transmission_coefficient": transmission })
+}
+
+/// Unnormalized radial probability density over a 2D grid (z = 0 plane).
+///
+/// The radial part is approximated as `r^l * exp(-r / n)` (in Bohr radii);
+/// the associated Laguerre polynomial is left out, so radial nodes are not shown.
+fn hydrogen_orbital(parameters: &Value) -> Value {
+    let principal_n = param_f64(parameters, "n", 1.0).max(1.0);
+    let angular_l = param_f64(parameters, "l", 0.0);
+    let grid_size = param_usize(parameters, "grid_size", 20).max(2);
+    let extent_bohr_radii = param_f64(parameters, "extent_bohr_radii", 10.0);
+
+    let probability_grid: Vec<Vec<f64>> = (0..grid_size)
+        .map(|row| {
+            (0..grid_size)
+                .map(|col| {
+                    let x = extent_bohr_radii * (col as f64 / (grid_size - 1) as f64 - 0.5);
+                    let y = extent_bohr_radii * (row as f64 / (grid_size - 1) as f64 - 0.5);
+                    let r = (x * x + y * y).sqrt();
+                    let radial = r.powf(angular_l) * (-r / principal_n).exp();
+                    radial * radial
+                })
+                .collect()
+        })
+        .collect();
+
+    json!({ "probability_grid": probability_grid })
+}